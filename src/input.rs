@@ -0,0 +1,281 @@
+use std::convert::TryFrom;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+
+/// Wraps a [`Read`] source so that [`Printer::print_all`](crate::Printer::print_all)
+/// can skip a prefix of bytes and stop after a bounded number of bytes have
+/// been produced, without reading the whole input into memory.
+///
+/// When the wrapped reader also implements [`Seek`], construct this with
+/// [`BoundedReader::new_seekable`] instead, so the skipped prefix is skipped
+/// with a real seek rather than drained byte by byte.
+pub struct BoundedReader<R> {
+    inner: R,
+    skip: u64,
+    length: Option<u64>,
+    consumed: u64,
+    skip_done: bool,
+}
+
+impl<R: Read> BoundedReader<R> {
+    /// Creates a reader that discards the first `skip` bytes of `inner` and,
+    /// if `length` is given, stops producing bytes once `length` of them
+    /// have been read.
+    pub fn new(inner: R, skip: u64, length: Option<u64>) -> Self {
+        BoundedReader {
+            inner,
+            skip,
+            length,
+            consumed: 0,
+            skip_done: skip == 0,
+        }
+    }
+
+    /// The absolute offset, within the original input, of the next byte
+    /// this reader will produce. Callers use this to set
+    /// [`Printer::display_offset`](crate::Printer::display_offset).
+    pub fn position(&self) -> u64 {
+        self.skip + self.consumed
+    }
+
+    fn drain_skip(&mut self) -> io::Result<()> {
+        let mut remaining = self.skip;
+        let mut discard = [0u8; 4096];
+        while remaining > 0 {
+            let want = usize::try_from(remaining.min(discard.len() as u64)).unwrap();
+            let n = self.inner.read(&mut discard[..want])?;
+            if n == 0 {
+                break;
+            }
+            remaining -= n as u64;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> BoundedReader<R> {
+    /// Like [`BoundedReader::new`], but fast-forwards past the skipped
+    /// prefix with a real seek instead of draining bytes.
+    pub fn new_seekable(mut inner: R, skip: u64, length: Option<u64>) -> io::Result<Self> {
+        let offset = i64::try_from(skip).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "skip count too large to seek")
+        })?;
+        inner.seek(SeekFrom::Current(offset))?;
+        Ok(BoundedReader {
+            inner,
+            skip,
+            length,
+            consumed: 0,
+            skip_done: true,
+        })
+    }
+}
+
+impl<R: Read> Read for BoundedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.skip_done {
+            self.drain_skip()?;
+            self.skip_done = true;
+        }
+
+        let want = match self.length {
+            Some(length) => {
+                let remaining = length.saturating_sub(self.consumed);
+                if remaining == 0 {
+                    return Ok(0);
+                }
+                usize::try_from(remaining.min(buf.len() as u64)).unwrap()
+            }
+            None => buf.len(),
+        };
+
+        let n = self.inner.read(&mut buf[..want])?;
+        self.consumed += n as u64;
+        Ok(n)
+    }
+}
+
+/// Which container format, if any, wraps the hexdumped bytes and needs to
+/// be stripped away before they're handed to [`Printer::print_all`](crate::Printer::print_all).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeMode {
+    None,
+    Gzip,
+    Zlib,
+    Yaz0,
+}
+
+/// Inserts the decoder selected by a [`DecodeMode`] between a source
+/// `Reader` and the byte loop, so hexyl dumps decompressed content rather
+/// than the raw compressed stream.
+pub enum Decoder<R> {
+    Identity(R),
+    Gzip(GzDecoder<R>),
+    Zlib(ZlibDecoder<R>),
+    Yaz0(Cursor<Vec<u8>>),
+}
+
+impl<R: Read> Decoder<R> {
+    pub fn new(mode: DecodeMode, inner: R) -> io::Result<Self> {
+        match mode {
+            DecodeMode::None => Ok(Decoder::Identity(inner)),
+            DecodeMode::Gzip => Ok(Decoder::Gzip(GzDecoder::new(inner))),
+            DecodeMode::Zlib => Ok(Decoder::Zlib(ZlibDecoder::new(inner))),
+            DecodeMode::Yaz0 => Ok(Decoder::Yaz0(Cursor::new(decode_yaz0(inner)?))),
+        }
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Decoder::Identity(r) => r.read(buf),
+            Decoder::Gzip(r) => r.read(buf),
+            Decoder::Zlib(r) => r.read(buf),
+            Decoder::Yaz0(r) => r.read(buf),
+        }
+    }
+}
+
+/// Hard cap on a Yaz0 stream's decoded size. `decode_yaz0` has to
+/// materialize the whole output before any of it can be dumped (its
+/// back-references index into output produced earlier in the same
+/// decode), so unlike the streaming Gzip/Zlib decoders it needs an
+/// explicit bomb guard: a few bytes of back-reference tokens can each
+/// expand to hundreds of output bytes with no other limit on the total.
+const YAZ0_MAX_DECODED_SIZE: usize = 1 << 30;
+
+/// Decodes a raw Yaz0-style group-flag LZ stream (the scheme used by
+/// Nintendo's `Yaz0` container, minus its magic/size header, which callers
+/// are expected to have already consumed).
+///
+/// Each group is a 1-byte header whose 8 bits, MSB first, flag the next 8
+/// tokens: a set bit copies one literal byte, a clear bit reads a 2-byte
+/// back-reference. The high nibble of the reference's first byte, if
+/// nonzero, gives a length of `nibble + 2`; if zero, the length is an
+/// extra following byte plus `0x12`. The remaining 12 bits of the
+/// reference, plus one, are a backward distance into the output, and the copy
+/// proceeds byte by byte so overlapping copies (length > distance) work.
+fn decode_yaz0<R: Read>(mut reader: R) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut one = [0u8; 1];
+
+    'outer: loop {
+        if reader.read(&mut one)? == 0 {
+            break;
+        }
+        let flags = one[0];
+
+        for i in (0..8).rev() {
+            // Enforced once per token, before any bytes are appended, so
+            // neither a run of literals nor a run of back-references can
+            // grow `out` past the limit.
+            if out.len() >= YAZ0_MAX_DECODED_SIZE {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "yaz0 stream decoded past the size limit; refusing to continue",
+                ));
+            }
+
+            let is_literal = (flags >> i) & 1 == 1;
+
+            if is_literal {
+                if reader.read(&mut one)? == 0 {
+                    break 'outer;
+                }
+                out.push(one[0]);
+            } else {
+                let mut pair = [0u8; 2];
+                if reader.read_exact(&mut pair).is_err() {
+                    break 'outer;
+                }
+
+                let high_nibble = pair[0] >> 4;
+                let length = if high_nibble != 0 {
+                    usize::from(high_nibble) + 2
+                } else {
+                    let mut extra = [0u8; 1];
+                    if reader.read(&mut extra)? == 0 {
+                        break 'outer;
+                    }
+                    usize::from(extra[0]) + 0x12
+                };
+                let distance =
+                    usize::from(u16::from(pair[0] & 0x0f) << 8 | u16::from(pair[1])) + 1;
+
+                let start = out.len().checked_sub(distance).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "yaz0 back-reference distance exceeds decoded output so far",
+                    )
+                })?;
+                if out.len() + length > YAZ0_MAX_DECODED_SIZE {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "yaz0 stream decoded past the size limit; refusing to continue",
+                    ));
+                }
+                for j in 0..length {
+                    let b = out[start + j];
+                    out.push(b);
+                }
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_and_bounds_a_plain_reader() {
+        let mut reader = BoundedReader::new(Cursor::new(b"0123456789".to_vec()), 2, Some(4));
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"2345");
+    }
+
+    #[test]
+    fn seekable_constructor_tracks_position() {
+        let mut reader =
+            BoundedReader::new_seekable(Cursor::new(b"0123456789".to_vec()), 3, Some(2)).unwrap();
+        assert_eq!(reader.position(), 3);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"34");
+        assert_eq!(reader.position(), 5);
+    }
+
+    #[test]
+    fn unbounded_length_reads_to_end() {
+        let mut reader = BoundedReader::new(Cursor::new(b"abcdef".to_vec()), 1, None);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"bcdef");
+    }
+
+    #[test]
+    fn yaz0_decodes_literal_run() {
+        // flags 0xF0: the first four tokens are literals "spam"; the
+        // stream then runs out before the remaining (back-reference)
+        // tokens in the group, which just ends decoding.
+        let compressed = [0xF0, b's', b'p', b'a', b'm'];
+        let decoded = decode_yaz0(Cursor::new(compressed.to_vec())).unwrap();
+        assert_eq!(decoded, b"spam");
+    }
+
+    #[test]
+    fn yaz0_decodes_overlapping_back_reference() {
+        // flags 0x80: one literal 'a', then a back-reference of length 4
+        // at distance 1, i.e. repeat the last byte four more times. The
+        // copy has to proceed byte-by-byte since it overlaps the bytes
+        // it's still producing.
+        let compressed = [0x80, b'a', 0x20, 0x00];
+        let decoded = decode_yaz0(Cursor::new(compressed.to_vec())).unwrap();
+        assert_eq!(decoded, b"aaaaa");
+    }
+}
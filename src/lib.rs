@@ -15,6 +15,14 @@ use crate::squeezer::{SqueezeAction, Squeezer};
 
 const BUFFER_SIZE: usize = 256;
 
+/// Hard cap on how much input `print_array` will buffer. Unlike the
+/// per-byte hexdump loop in `print_all`, the array-literal output mode
+/// needs the whole input's length up front (it's written into the array
+/// declaration), so it can't stream; this bounds how large an input it'll
+/// materialize in memory before giving up, so a huge or never-ending
+/// input (or one fed through an uncapped decoder) can't exhaust memory.
+const ARRAY_MAX_BUFFERED_SIZE: u64 = 1 << 30;
+
 const COLOR_NULL: Color = Fixed(242); // grey
 const COLOR_OFFSET: Color = Fixed(242); // grey
 const COLOR_ASCII_PRINTABLE: Color = Color::Cyan;
@@ -120,6 +128,120 @@ impl Byte {
     }
 }
 
+/// The numeric base used to render each byte in the hex panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseFormat {
+    LowerHex,
+    UpperHex,
+    Octal,
+    Binary,
+    Decimal,
+}
+
+impl BaseFormat {
+    /// The width, in characters, of one formatted byte cell, including its
+    /// trailing separator space.
+    fn cell_width(self) -> usize {
+        match self {
+            BaseFormat::LowerHex | BaseFormat::UpperHex => 3,
+            BaseFormat::Octal => 4,
+            BaseFormat::Binary => 9,
+            BaseFormat::Decimal => 4,
+        }
+    }
+
+    fn format_byte(self, b: u8) -> String {
+        match self {
+            BaseFormat::LowerHex => format!("{:02x} ", b),
+            BaseFormat::UpperHex => format!("{:02X} ", b),
+            BaseFormat::Octal => format!("{:03o} ", b),
+            BaseFormat::Binary => format!("{:08b} ", b),
+            BaseFormat::Decimal => format!("{:03} ", b),
+        }
+    }
+}
+
+impl Default for BaseFormat {
+    fn default() -> Self {
+        BaseFormat::LowerHex
+    }
+}
+
+impl BaseFormat {
+    /// Renders `b` as a single element of a source-code array literal in
+    /// the given `language`, including any base prefix that language uses.
+    fn array_element(self, b: u8, language: Language) -> String {
+        match (self, language) {
+            (BaseFormat::LowerHex, _) => format!("0x{:02x}", b),
+            (BaseFormat::UpperHex, _) => format!("0x{:02X}", b),
+            (BaseFormat::Octal, Language::Rust) => format!("0o{:03o}", b),
+            (BaseFormat::Octal, Language::C) => format!("0{:03o}", b),
+            (BaseFormat::Binary, _) => format!("0b{:08b}", b),
+            (BaseFormat::Decimal, _) => format!("{}", b),
+        }
+    }
+}
+
+/// Target language for the array-literal output mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    C,
+}
+
+/// Configures the array-literal output mode: which language's declaration
+/// syntax to emit, which base to render elements in, and how many elements
+/// to place on each line.
+#[derive(Debug, Clone, Copy)]
+pub struct ArrayFormat {
+    pub language: Language,
+    pub base: BaseFormat,
+    pub elements_per_line: usize,
+}
+
+impl ArrayFormat {
+    pub fn new(language: Language, base: BaseFormat, elements_per_line: usize) -> Self {
+        Self {
+            language,
+            base,
+            elements_per_line,
+        }
+    }
+}
+
+/// A sink that pre-built, known-length strings (such as the entries of
+/// `byte_hex_table`/`byte_char_table`) can be appended to without going
+/// through `core::fmt`'s formatter machinery on every call.
+trait DisplaySink {
+    fn write_byte(&mut self, byte: u8);
+
+    fn write_fixed_size(&mut self, s: &str) {
+        for &b in s.as_bytes() {
+            self.write_byte(b);
+        }
+    }
+}
+
+impl DisplaySink for Vec<u8> {
+    fn write_byte(&mut self, byte: u8) {
+        self.push(byte);
+    }
+
+    fn write_fixed_size(&mut self, s: &str) {
+        self.extend_from_slice(s.as_bytes());
+    }
+}
+
+impl DisplaySink for String {
+    fn write_byte(&mut self, byte: u8) {
+        self.push(byte as char);
+    }
+
+    fn write_fixed_size(&mut self, s: &str) {
+        self.push_str(s);
+    }
+}
+
 struct BorderElements {
     left_corner: char,
     horizontal_line: char,
@@ -202,6 +324,9 @@ pub struct Printer<'a, Writer: Write> {
     squeezer: Squeezer,
     display_offset: u64,
     window_size: WindowSize,
+    base: BaseFormat,
+    array_format: Option<ArrayFormat>,
+    array_element_table: Vec<String>,
 }
 
 impl<'a, Writer: Write> Printer<'a, Writer> {
@@ -219,16 +344,7 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
             show_color,
             border_style,
             header_was_printed: false,
-            byte_hex_table: (0u8..=u8::max_value())
-                .map(|i| {
-                    let byte_hex = format!("{:02x} ", i);
-                    if show_color {
-                        Byte(i).color().paint(byte_hex).to_string()
-                    } else {
-                        byte_hex
-                    }
-                })
-                .collect(),
+            byte_hex_table: Self::build_byte_table(BaseFormat::default(), show_color),
             byte_char_table: (0u8..=u8::max_value())
                 .map(|i| {
                     let byte_char = format!("{}", Byte(i).as_char());
@@ -242,9 +358,25 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
             squeezer: Squeezer::new(use_squeeze),
             display_offset: 0,
             window_size: WindowSize::new(16).unwrap(),
+            base: BaseFormat::default(),
+            array_format: None,
+            array_element_table: vec![],
         }
     }
 
+    fn build_byte_table(base: BaseFormat, show_color: bool) -> Vec<String> {
+        (0u8..=u8::max_value())
+            .map(|i| {
+                let byte_str = base.format_byte(i);
+                if show_color {
+                    Byte(i).color().paint(byte_str).to_string()
+                } else {
+                    byte_str
+                }
+            })
+            .collect()
+    }
+
     pub fn display_offset(&mut self, display_offset: u64) -> &mut Self {
         self.display_offset = display_offset;
         self
@@ -255,9 +387,27 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
         self
     }
 
+    /// Selects the numeric base used to render each byte in the hex panel.
+    pub fn base(&mut self, base: BaseFormat) -> &mut Self {
+        self.base = base;
+        self.byte_hex_table = Self::build_byte_table(base, self.show_color);
+        self
+    }
+
+    /// Switches to the array-literal output mode, suppressing the usual
+    /// framed hex table in favor of a source-code array declaration.
+    pub fn array_format(&mut self, array_format: ArrayFormat) -> &mut Self {
+        self.array_element_table = (0u8..=u8::max_value())
+            .map(|i| array_format.base.array_element(i, array_format.language))
+            .collect();
+        self.array_format = Some(array_format);
+        self
+    }
+
     fn print_border_elements<W>(
         writer: &mut W,
         window_size: WindowSize,
+        byte_cell_width: usize,
         border_elements: BorderElements,
     ) where
         W: Write,
@@ -268,7 +418,7 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
         let side_segment = h.to_string().repeat(half_window_size);
         let main_segment = h.to_string().repeat(
             half_window_size
-                .checked_mul(3)
+                .checked_mul(byte_cell_width)
                 .unwrap()
                 .checked_add(1)
                 .unwrap(),
@@ -291,24 +441,45 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
         let &mut Self {
             ref border_style,
             window_size,
+            base,
             ref mut writer,
             ..
         } = self;
         border_style
             .header_elems()
-            .map(|bes| Self::print_border_elements(writer, window_size, bes));
+            .map(|bes| Self::print_border_elements(writer, window_size, base.cell_width(), bes));
     }
 
     pub fn footer(&mut self) {
         let &mut Self {
             ref border_style,
             window_size,
+            base,
             ref mut writer,
             ..
         } = self;
         border_style
             .footer_elems()
-            .map(|bes| Self::print_border_elements(writer, window_size, bes));
+            .map(|bes| Self::print_border_elements(writer, window_size, base.cell_width(), bes));
+    }
+
+    /// The number of bytes a full line (cells, separators and border) is
+    /// expected to take once rendered, used to pre-reserve `buffer_line`
+    /// before it's built up one byte at a time.
+    fn line_capacity(&self) -> usize {
+        let full_window_size = usize::try_from(self.window_size.full::<u16>()).unwrap_or(0);
+        let hex_panel_width = full_window_size * self.base.cell_width();
+        // Text-panel characters such as '•' take up to 3 bytes in UTF-8.
+        let ascii_panel_width = full_window_size * 3;
+        // ANSI color escapes add up to 15 bytes around each hex cell, each
+        // text-panel character and the offset; over-reserving a little is
+        // cheaper than a realloc mid-line.
+        let color_slack = if self.show_color { full_window_size * 30 + 15 } else { 0 };
+        // Offset (a u64 takes up to 16 hex digits; it is zero-padded to half
+        // the window), six separators of up to 3 bytes each, two spaces and
+        // the newline.
+        let offset_width = (full_window_size / 2).max(16);
+        hex_panel_width + ascii_panel_width + color_slack + offset_width + 6 * 3 + 3
     }
 
     fn print_position_indicator(&mut self) {
@@ -317,6 +488,8 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
             self.header_was_printed = true;
         }
 
+        self.buffer_line.reserve(self.line_capacity());
+
         let style = COLOR_OFFSET.normal();
         let byte_index = format!(
             "{:0alignment$x}",
@@ -345,7 +518,8 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
             self.print_position_indicator();
         }
 
-        write!(&mut self.buffer_line, "{}", self.byte_hex_table[b as usize])?;
+        let hex: &str = &self.byte_hex_table[b as usize];
+        self.buffer_line.write_fixed_size(hex);
         self.raw_line.push(b);
 
         self.squeezer.process(self.window_size, b, self.idx);
@@ -366,10 +540,12 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
     }
 
     pub fn print_textline(&mut self) -> io::Result<()> {
+        let cell_width = self.base.cell_width();
+
         assert!(
             usize::try_from(self.window_size.half::<u16>())
                 .ok()
-                .and_then(|ws| ws.checked_mul(3).and_then(|s| s.checked_add(1)))
+                .and_then(|ws| ws.checked_mul(cell_width).and_then(|s| s.checked_add(1)))
                 .is_some(),
             "window size calculations exceed usize range",
         );
@@ -385,8 +561,8 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
                     &mut self.buffer_line,
                     "{0:1$}{4}{0:2$}{5}{0:3$}{4}{0:3$}{5}",
                     "",
-                    half_window_size * 3,
-                    half_window_size * 3 + 1,
+                    half_window_size * cell_width,
+                    half_window_size * cell_width + 1,
                     half_window_size,
                     self.border_style.inner_sep(),
                     self.border_style.outer_sep(),
@@ -404,8 +580,8 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
                     &mut self.buffer_line,
                     "{0:1$}{3}{0:2$}{4}",
                     "",
-                    3 * (half_window_size - len),
-                    half_window_size * 3 + 1,
+                    cell_width * (half_window_size - len),
+                    half_window_size * cell_width + 1,
                     self.border_style.inner_sep(),
                     self.border_style.outer_sep(),
                 );
@@ -414,18 +590,15 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
                     &mut self.buffer_line,
                     "{0:1$}{2}",
                     "",
-                    3 * (half_window_size * 2 - len),
+                    cell_width * (half_window_size * 2 - len),
                     self.border_style.outer_sep()
                 );
             }
 
             let mut idx = 1;
             for &b in self.raw_line.iter() {
-                let _ = write!(
-                    &mut self.buffer_line,
-                    "{}",
-                    self.byte_char_table[b as usize]
-                );
+                let ch: &str = &self.byte_char_table[b as usize];
+                self.buffer_line.write_fixed_size(ch);
 
                 if idx == half_window_size {
                     let _ = write!(&mut self.buffer_line, "{}", self.border_style.inner_sep());
@@ -470,7 +643,7 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
                     asterisk,
                     "",
                     half_window_size - 1,
-                    half_window_size * 3 + 1,
+                    half_window_size * cell_width + 1,
                     half_window_size,
                     self.border_style.outer_sep(),
                     self.border_style.inner_sep(),
@@ -500,6 +673,10 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
         &mut self,
         mut reader: Reader,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(array_format) = self.array_format {
+            return self.print_array(reader, array_format);
+        }
+
         let mut buffer = [0; BUFFER_SIZE];
         'mainloop: loop {
             let size = reader.read(&mut buffer)?;
@@ -526,6 +703,53 @@ impl<'a, Writer: Write> Printer<'a, Writer> {
 
         Ok(())
     }
+
+    /// Renders the contents of `reader` as a source-code array literal
+    /// instead of the usual framed hex table, per `print_all`'s array-mode
+    /// branch.
+    fn print_array<Reader: Read>(
+        &mut self,
+        mut reader: Reader,
+        array_format: ArrayFormat,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        (&mut reader)
+            .take(ARRAY_MAX_BUFFERED_SIZE + 1)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() as u64 > ARRAY_MAX_BUFFERED_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "input too large to buffer for array output; bound it with --length",
+            )
+            .into());
+        }
+
+        let (decl, close) = match array_format.language {
+            Language::Rust => (
+                format!("let DATA: [u8; {}] = [\n", bytes.len()),
+                "];\n",
+            ),
+            Language::C => (
+                format!("unsigned char data[{}] = {{\n", bytes.len()),
+                "};\n",
+            ),
+        };
+        self.writer.write_all(decl.as_bytes())?;
+
+        let elements_per_line = array_format.elements_per_line.max(1);
+        for chunk in bytes.chunks(elements_per_line) {
+            let line = chunk
+                .iter()
+                .map(|&b| self.array_element_table[b as usize].as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(self.writer, "    {},", line)?;
+        }
+
+        self.writer.write_all(close.as_bytes())?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -589,4 +813,79 @@ mod tests {
         let actual_string: &str = str::from_utf8(&output).unwrap();
         assert_eq!(actual_string, expected_string)
     }
+
+    fn assert_base_output(base: BaseFormat, expected_string: String) {
+        let input = io::Cursor::new(b"spam");
+        let mut output = vec![];
+        let mut printer = Printer::new(&mut output, false, BorderStyle::Unicode, true);
+        printer.base(base);
+
+        printer.print_all(input).unwrap();
+
+        let actual_string: &str = str::from_utf8(&output).unwrap();
+        assert_eq!(actual_string, expected_string)
+    }
+
+    #[test]
+    fn octal_base_widens_cells_and_border() {
+        let expected_string = "\
+┌────────┬─────────────────────────────────┬─────────────────────────────────┬────────┬────────┐
+│00000000│ 163 160 141 155                 ┊                                 │spam    ┊        │
+└────────┴─────────────────────────────────┴─────────────────────────────────┴────────┴────────┘
+"
+        .to_owned();
+        assert_base_output(BaseFormat::Octal, expected_string);
+    }
+
+    #[test]
+    fn binary_base_widens_cells_and_border() {
+        let expected_string = "\
+┌────────┬─────────────────────────────────────────────────────────────────────────┬─────────────────────────────────────────────────────────────────────────┬────────┬────────┐
+│00000000│ 01110011 01110000 01100001 01101101                                     ┊                                                                         │spam    ┊        │
+└────────┴─────────────────────────────────────────────────────────────────────────┴─────────────────────────────────────────────────────────────────────────┴────────┴────────┘
+"
+        .to_owned();
+        assert_base_output(BaseFormat::Binary, expected_string);
+    }
+
+    #[test]
+    fn array_format_emits_rust_literal_wrapped_per_line() {
+        let input = io::Cursor::new(b"spammy!");
+        let expected_string = "\
+let DATA: [u8; 7] = [
+    0x73, 0x70, 0x61, 0x6d,
+    0x6d, 0x79, 0x21,
+];
+"
+        .to_owned();
+
+        let mut output = vec![];
+        let mut printer = Printer::new(&mut output, false, BorderStyle::Unicode, true);
+        printer.array_format(ArrayFormat::new(Language::Rust, BaseFormat::LowerHex, 4));
+        printer.print_all(input).unwrap();
+
+        let actual_string: &str = str::from_utf8(&output).unwrap();
+        assert_eq!(actual_string, expected_string)
+    }
+
+    #[test]
+    fn array_format_emits_c_octal_literal_wrapped_per_line() {
+        let input = io::Cursor::new(b"spammy!");
+        let expected_string = "\
+unsigned char data[7] = {
+    0163, 0160, 0141,
+    0155, 0155, 0171,
+    0041,
+};
+"
+        .to_owned();
+
+        let mut output = vec![];
+        let mut printer = Printer::new(&mut output, false, BorderStyle::Unicode, true);
+        printer.array_format(ArrayFormat::new(Language::C, BaseFormat::Octal, 3));
+        printer.print_all(input).unwrap();
+
+        let actual_string: &str = str::from_utf8(&output).unwrap();
+        assert_eq!(actual_string, expected_string)
+    }
 }